@@ -1,4 +1,8 @@
 use crate::{error::Result, Error};
+use figment::{
+    providers::{Env, Format, Toml},
+    Figment,
+};
 use url::Url;
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -22,6 +26,13 @@ pub struct Config {
     #[cfg(feature = "metrics")]
     #[serde(default)]
     pub metrics: crate::metrics::MetricsConfig,
+
+    #[cfg(feature = "telemetry-server")]
+    #[serde(default)]
+    pub telemetry_server: crate::telemetry_server::TelemetryServerConfig,
+
+    #[serde(default)]
+    pub error_level: ErrorLevel,
 }
 
 impl Config {
@@ -32,6 +43,31 @@ impl Config {
     fn default_service_version() -> String {
         env!("CARGO_PKG_VERSION").to_owned()
     }
+
+    // Layers an optional pulse.toml (or config_path) under PULSE_-prefixed,
+    // double-underscore-nested env vars, e.g. PULSE_TRACE__LEVEL=debug.
+    pub fn figment(config_path: Option<&str>) -> Figment {
+        Figment::new()
+            .merge(Toml::file(config_path.unwrap_or("pulse.toml")))
+            .merge(Env::prefixed("PULSE_").split("__"))
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Self::from_figment(Self::figment(None))
+    }
+
+    fn from_figment(figment: Figment) -> Result<Self> {
+        let mut config: Config = figment.extract()?;
+
+        if config.uri.is_none() {
+            if let Ok(network) = figment.extract_inner::<crate::options::NetworkOptions>("network")
+            {
+                config.uri = Some(network.uri());
+            }
+        }
+
+        Ok(config)
+    }
 }
 
 impl Default for Config {
@@ -49,10 +85,83 @@ impl Default for Config {
 
             #[cfg(feature = "metrics")]
             metrics: Default::default(),
+
+            #[cfg(feature = "telemetry-server")]
+            telemetry_server: Default::default(),
+
+            error_level: ErrorLevel::default(),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorLevel {
+    Error,
+    Warn,
+}
+
+impl Default for ErrorLevel {
+    fn default() -> Self {
+        Self::Error
+    }
+}
+
+#[derive(Debug, Clone, Copy, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportMode {
+    Batch,
+    Simple,
+}
+
+impl Default for ExportMode {
+    fn default() -> Self {
+        Self::Batch
+    }
+}
+
+// Only consulted when ExportMode::Batch is selected; unset fields fall back
+// to the SDK's own defaults.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct BatchExportConfig {
+    pub max_queue_size: Option<usize>,
+
+    #[serde(default)]
+    #[serde(deserialize_with = "deserialize_optional_duration")]
+    pub scheduled_delay: Option<std::time::Duration>,
+
+    pub max_export_batch_size: Option<usize>,
+}
+
+pub(crate) fn build_batch_config(config: &BatchExportConfig) -> opentelemetry_sdk::trace::BatchConfig {
+    let mut builder = opentelemetry_sdk::trace::BatchConfigBuilder::default();
+
+    if let Some(max_queue_size) = config.max_queue_size {
+        builder = builder.with_max_queue_size(max_queue_size);
+    }
+
+    if let Some(scheduled_delay) = config.scheduled_delay {
+        builder = builder.with_scheduled_delay(scheduled_delay);
+    }
+
+    if let Some(max_export_batch_size) = config.max_export_batch_size {
+        builder = builder.with_max_export_batch_size(max_export_batch_size);
+    }
+
+    builder.build()
+}
+
+fn deserialize_optional_duration<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<std::time::Duration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    <Option<String> as serde::Deserialize>::deserialize(deserializer)?
+        .map(|s| duration_str::parse(&s).map_err(serde::de::Error::custom))
+        .transpose()
+}
+
 pub(crate) enum UriScheme {
     Https,
     Http,
@@ -8,6 +8,9 @@ pub enum Error {
     #[error("unsupported url scheme: {0}")]
     UnsupportedUrlScheme(String),
 
+    #[error("{0}")]
+    Figment(#[from] figment::Error),
+
     #[cfg(feature = "logs")]
     #[error("{0}")]
     SetLoggerError(#[from] log::SetLoggerError),
@@ -23,4 +26,12 @@ pub enum Error {
     #[cfg(feature = "metrics")]
     #[error("{0}")]
     OtelMetric(#[from] opentelemetry_sdk::metrics::MetricError),
+
+    #[cfg(feature = "telemetry-server")]
+    #[error("invalid telemetry server address: {0}")]
+    InvalidServerAddress(#[from] std::net::AddrParseError),
+
+    #[cfg(feature = "telemetry-server")]
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
 }
@@ -1,5 +1,6 @@
 mod config;
 mod error;
+pub(crate) mod options;
 mod session;
 
 #[cfg(feature = "logs")]
@@ -8,13 +9,19 @@ pub mod log;
 #[cfg(feature = "trace")]
 pub mod trace;
 
+#[cfg(feature = "trace")]
+pub mod propagation;
+
 #[cfg(feature = "metrics")]
 pub mod metrics;
 
+#[cfg(feature = "telemetry-server")]
+pub mod telemetry_server;
+
 pub use {
     config::Config,
     error::Error,
-    session::{init, Session},
+    session::{init, set_error_handler, Session},
 };
 
 #[cfg(feature = "otel-api")]
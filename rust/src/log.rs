@@ -1,5 +1,5 @@
 use crate::{
-    config::{Config, UriScheme, UrlExt},
+    config::{BatchExportConfig, Config, ExportMode, UriScheme, UrlExt},
     error::Result,
 };
 use env_logger::fmt::{
@@ -8,9 +8,15 @@ use env_logger::fmt::{
 };
 use log::Record;
 use opentelemetry_appender_log::OpenTelemetryLogBridge;
+use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use opentelemetry_otlp::{LogExporter, WithExportConfig};
-use opentelemetry_sdk::{logs::LoggerProvider as OtelLoggerProvider, runtime, Resource};
-use std::io::Write as _;
+use opentelemetry_sdk::{
+    logs::{BatchLogProcessor, LoggerProvider as OtelLoggerProvider, SimpleLogProcessor},
+    runtime, Resource,
+};
+use std::io::{IsTerminal as _, Write as _};
+use tracing::{level_filters::LevelFilter, Subscriber};
+use tracing_subscriber::{registry::LookupSpan, Layer};
 use url::Url;
 
 // rexporting log macros
@@ -23,6 +29,29 @@ pub struct LogConfig {
 
     #[serde(default)]
     pub extra_modules: Vec<String>,
+
+    #[serde(default)]
+    pub format: LogFormat,
+
+    #[serde(default)]
+    pub export_mode: ExportMode,
+
+    #[serde(default)]
+    pub batch: BatchExportConfig,
+}
+
+#[derive(serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    Pretty,
+    Compact,
+    Json,
+}
+
+impl Default for LogFormat {
+    fn default() -> Self {
+        Self::Pretty
+    }
 }
 
 #[derive(serde::Deserialize, Debug, Clone, Copy)]
@@ -75,12 +104,23 @@ impl LoggerProvider {
                     .build()?,
             };
 
-            Some(
-                OtelLoggerProvider::builder()
-                    .with_resource(otel_resource)
-                    .with_batch_exporter(exporter, runtime::Tokio)
-                    .build(),
-            )
+            let builder = OtelLoggerProvider::builder().with_resource(otel_resource);
+
+            let provider = match config.log.export_mode {
+                ExportMode::Simple => {
+                    builder.with_log_processor(SimpleLogProcessor::new(Box::new(exporter)))
+                }
+                ExportMode::Batch => {
+                    let processor = BatchLogProcessor::builder(exporter, runtime::Tokio)
+                        .with_batch_config(crate::config::build_batch_config(&config.log.batch))
+                        .build();
+
+                    builder.with_log_processor(processor)
+                }
+            }
+            .build();
+
+            Some(provider)
         } else {
             None
         };
@@ -96,6 +136,18 @@ impl LoggerProvider {
         Logger::new(self, &self.service_name, &self.config)
             .map(|l| Box::new(l) as Box<dyn log::Log>)
     }
+
+    // Bridges `tracing` events (not just `log` crate records) to the same
+    // OTLP log pipeline.
+    pub(crate) fn layer<S>(&self) -> Option<impl Layer<S>>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        self.otel_log_provider
+            .as_ref()
+            .map(OpenTelemetryTracingBridge::new)
+            .map(|l| l.with_filter(LevelFilter::from_level(self.config.level.into())))
+    }
 }
 
 impl Drop for LoggerProvider {
@@ -128,7 +180,7 @@ impl Logger {
         let std_logger = {
             let level: log::Level = config.level.into();
             log::set_max_level(level.to_level_filter());
-            let styler = Styler::new(service_name);
+            let styler = Styler::new(service_name, config.format);
 
             let mut builder = env_logger::Builder::new();
             builder.filter(Some(service_name), level.to_level_filter());
@@ -154,7 +206,25 @@ impl log::Log for Logger {
 
     fn log(&self, record: &Record) {
         self.std_logger.log(record);
+
         if let Some(ref otel_logger) = self.otel_logger.as_ref() {
+            #[cfg(feature = "trace")]
+            if let Some(request_id) = crate::trace::current_request_id() {
+                let source = RequestIdSource(&request_id);
+                let enriched = Record::builder()
+                    .args(*record.args())
+                    .level(record.level())
+                    .target(record.target())
+                    .module_path(record.module_path())
+                    .file(record.file())
+                    .line(record.line())
+                    .key_values(&source)
+                    .build();
+
+                otel_logger.log(&enriched);
+                return;
+            }
+
             otel_logger.log(record);
         }
     }
@@ -167,8 +237,20 @@ impl log::Log for Logger {
     }
 }
 
+#[cfg(feature = "trace")]
+struct RequestIdSource<'a>(&'a str);
+
+#[cfg(feature = "trace")]
+impl<'a> log::kv::Source for RequestIdSource<'a> {
+    fn visit<'kvs>(&'kvs self, visitor: &mut dyn log::kv::Visitor<'kvs>) -> Result<(), log::kv::Error> {
+        visitor.visit_pair("request.id".into(), self.0.into())
+    }
+}
+
 struct Styler {
     service_name: String,
+    format: LogFormat,
+    colorize: bool,
     timestamp_style: Style,
     service_name_style: Style,
     error_style: Style,
@@ -179,18 +261,28 @@ struct Styler {
 }
 
 impl Styler {
-    pub fn new(service_name: impl Into<String>) -> Self {
+    pub fn new(service_name: impl Into<String>, format: LogFormat) -> Self {
+        let colorize = format != LogFormat::Json && std::io::stdout().is_terminal();
+
+        let style = |color: AnsiColor| {
+            if colorize {
+                Style::new().fg_color(Some(color.into()))
+            } else {
+                Style::new()
+            }
+        };
+
         Self {
             service_name: service_name.into(),
-            timestamp_style: Style::new()
-                .fg_color(Some(AnsiColor::Black.into()))
-                .italic(),
-            service_name_style: Style::new().fg_color(Some(AnsiColor::Black.into())).bold(),
-            error_style: Style::new().fg_color(Some(AnsiColor::Red.into())),
-            warn_style: Style::new().fg_color(Some(AnsiColor::Yellow.into())),
-            info_style: Style::new().fg_color(Some(AnsiColor::Green.into())),
-            debug_style: Style::new().fg_color(Some(AnsiColor::Blue.into())),
-            trace_style: Style::new().fg_color(Some(AnsiColor::BrightWhite.into())),
+            format,
+            colorize,
+            timestamp_style: style(AnsiColor::Black).italic(),
+            service_name_style: style(AnsiColor::Black).bold(),
+            error_style: style(AnsiColor::Red),
+            warn_style: style(AnsiColor::Yellow),
+            info_style: style(AnsiColor::Green),
+            debug_style: style(AnsiColor::Blue),
+            trace_style: style(AnsiColor::BrightWhite),
         }
     }
 
@@ -199,28 +291,113 @@ impl Styler {
         buf: &mut Formatter,
         record: &Record,
     ) -> std::result::Result<(), std::io::Error> {
-        let timestamp_style = &self.timestamp_style;
-        let level_style = match record.level() {
+        match self.format {
+            LogFormat::Pretty => self.format_pretty(buf, record),
+            LogFormat::Compact => self.format_compact(buf, record),
+            LogFormat::Json => self.format_json(buf, record),
+        }
+    }
+
+    fn level_style(&self, level: log::Level) -> &Style {
+        match level {
             log::Level::Error => &self.error_style,
             log::Level::Warn => &self.warn_style,
             log::Level::Info => &self.info_style,
             log::Level::Debug => &self.debug_style,
             log::Level::Trace => &self.trace_style,
-        };
+        }
+    }
+
+    #[cfg(feature = "trace")]
+    fn request_id(&self) -> Option<std::sync::Arc<str>> {
+        crate::trace::current_request_id()
+    }
+
+    #[cfg(not(feature = "trace"))]
+    fn request_id(&self) -> Option<std::sync::Arc<str>> {
+        None
+    }
+
+    fn format_pretty(
+        &self,
+        buf: &mut Formatter,
+        record: &Record,
+    ) -> std::result::Result<(), std::io::Error> {
+        let timestamp_style = &self.timestamp_style;
+        let level_style = self.level_style(record.level());
         let service_name_style = &self.service_name_style;
         let loc = record
             .module_path()
             .map(|p| format!("[{p}]"))
             .unwrap_or_default();
+        let request_id = self
+            .request_id()
+            .map(|id| format!(" request.id={id}"))
+            .unwrap_or_default();
 
         writeln!(
             buf,
-            "{timestamp_style}{}{timestamp_style:#} [{service_name_style}{}{service_name_style:#}:{level_style}{:5}{level_style:#}] {} {}",
+            "{timestamp_style}{}{timestamp_style:#} [{service_name_style}{}{service_name_style:#}:{level_style}{:5}{level_style:#}] {} {}{}",
             buf.timestamp(),
             &self.service_name,
             record.level(),
             loc,
             record.args(),
+            request_id,
+        )
+    }
+
+    fn format_compact(
+        &self,
+        buf: &mut Formatter,
+        record: &Record,
+    ) -> std::result::Result<(), std::io::Error> {
+        let level_style = self.level_style(record.level());
+        let request_id = self
+            .request_id()
+            .map(|id| format!(" request.id={id}"))
+            .unwrap_or_default();
+
+        writeln!(
+            buf,
+            "{} {level_style}{:5}{level_style:#} {}: {}{}",
+            buf.timestamp(),
+            record.level(),
+            &self.service_name,
+            record.args(),
+            request_id,
         )
     }
+
+    fn format_json(
+        &self,
+        buf: &mut Formatter,
+        record: &Record,
+    ) -> std::result::Result<(), std::io::Error> {
+        let line = JsonLogRecord {
+            timestamp: buf.timestamp().to_string(),
+            level: record.level().as_str(),
+            target: record.target(),
+            service_name: &self.service_name,
+            message: record.args().to_string(),
+            request_id: self.request_id().map(|id| id.to_string()),
+        };
+
+        let json = serde_json::to_string(&line)
+            .unwrap_or_else(|e| format!(r#"{{"error":"failed to serialize log record: {e}"}}"#));
+
+        writeln!(buf, "{json}")
+    }
+}
+
+#[derive(serde::Serialize)]
+struct JsonLogRecord<'a> {
+    timestamp: String,
+    level: &'a str,
+    target: &'a str,
+    #[serde(rename = "service.name")]
+    service_name: &'a str,
+    message: String,
+    #[serde(rename = "request.id", skip_serializing_if = "Option::is_none")]
+    request_id: Option<String>,
 }
@@ -2,11 +2,19 @@ use crate::{
     config::{Config, UriScheme, UrlExt},
     error::Result,
 };
-use opentelemetry_otlp::{MetricExporter, WithExportConfig};
+use opentelemetry_otlp::{
+    tonic::metadata::{MetadataMap, MetadataValue},
+    MetricExporter, Protocol, WithExportConfig,
+};
+use opentelemetry_sdk::metrics::Temporality as OtelTemporality;
 use opentelemetry_sdk::{
-    metrics::{PeriodicReader, SdkMeterProvider as OtelMeterProvider},
+    metrics::{
+        new_view, Aggregation, Instrument, PeriodicReader, SdkMeterProvider as OtelMeterProvider,
+        Stream,
+    },
     runtime, Resource,
 };
+use std::collections::HashMap;
 use std::time::Duration;
 use tracing::Subscriber;
 use tracing_opentelemetry::MetricsLayer;
@@ -18,6 +26,50 @@ pub struct MetricsConfig {
     #[serde(deserialize_with = "duration_str::deserialize_duration")]
     #[serde(default = "MetricsConfig::default_export_interval")]
     pub export_interval: Duration,
+
+    #[cfg(feature = "telemetry-server")]
+    #[serde(default)]
+    pub prometheus: bool,
+
+    #[serde(default)]
+    pub protocol: OtlpProtocol,
+
+    #[serde(default)]
+    pub temporality: Temporality,
+
+    #[serde(default)]
+    pub views: Vec<ViewConfig>,
+
+    // `${VAR}` values are resolved from the environment, so secrets don't need
+    // to live in the config file; if VAR is unset or empty the value is kept
+    // as written.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OtlpProtocol {
+    #[serde(rename = "http/protobuf")]
+    HttpProtobuf,
+
+    #[serde(rename = "http/json")]
+    HttpJson,
+}
+
+impl Default for OtlpProtocol {
+    fn default() -> Self {
+        Self::HttpProtobuf
+    }
+}
+
+impl From<OtlpProtocol> for Protocol {
+    fn from(value: OtlpProtocol) -> Self {
+        match value {
+            OtlpProtocol::HttpProtobuf => Protocol::HttpBinary,
+            OtlpProtocol::HttpJson => Protocol::HttpJson,
+        }
+    }
 }
 
 impl MetricsConfig {
@@ -26,10 +78,152 @@ impl MetricsConfig {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Temporality {
+    Cumulative,
+    Delta,
+}
+
+impl Default for Temporality {
+    fn default() -> Self {
+        Self::Cumulative
+    }
+}
+
+impl From<Temporality> for OtelTemporality {
+    fn from(value: Temporality) -> Self {
+        match value {
+            Temporality::Cumulative => OtelTemporality::Cumulative,
+            Temporality::Delta => OtelTemporality::Delta,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct ViewConfig {
+    pub instrument_name: String,
+
+    #[serde(default)]
+    pub unit: Option<String>,
+
+    pub aggregation: ViewAggregation,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewAggregation {
+    Drop,
+    LastValue,
+    ExplicitBucketHistogram { boundaries: Vec<f64> },
+}
+
+impl From<ViewAggregation> for Aggregation {
+    fn from(value: ViewAggregation) -> Self {
+        match value {
+            ViewAggregation::Drop => Aggregation::Drop,
+            ViewAggregation::LastValue => Aggregation::LastValue,
+            ViewAggregation::ExplicitBucketHistogram { boundaries } => {
+                Aggregation::ExplicitBucketHistogram {
+                    boundaries,
+                    record_min_max: true,
+                }
+            }
+        }
+    }
+}
+
+// Precedence per signal: metrics-specific env var, then the generic one, then
+// the typed config default.
+struct ResolvedMetricsConfig {
+    endpoint: Option<String>,
+    protocol: OtlpProtocol,
+    export_interval: Duration,
+    temporality: Temporality,
+    headers: HashMap<String, String>,
+}
+
+impl ResolvedMetricsConfig {
+    fn resolve(config: &Config) -> Self {
+        let endpoint = env_var("OTEL_EXPORTER_OTLP_METRICS_ENDPOINT")
+            .or_else(|| env_var("OTEL_EXPORTER_OTLP_ENDPOINT"))
+            .or_else(|| config.uri.clone());
+
+        let protocol = parse_protocol(env_var("OTEL_EXPORTER_OTLP_METRICS_PROTOCOL"))
+            .or_else(|| parse_protocol(env_var("OTEL_EXPORTER_OTLP_PROTOCOL")))
+            .unwrap_or(config.metrics.protocol);
+
+        let export_interval = env_var("OTEL_EXPORTER_OTLP_METRICS_EXPORT_INTERVAL")
+            .and_then(|ms| ms.parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(config.metrics.export_interval);
+
+        let headers = config
+            .metrics
+            .headers
+            .iter()
+            .map(|(k, v)| (k.clone(), resolve_header_value(v)))
+            .collect();
+
+        Self {
+            endpoint,
+            protocol,
+            export_interval,
+            temporality: config.metrics.temporality,
+            headers,
+        }
+    }
+}
+
+fn env_var(key: &str) -> Option<String> {
+    std::env::var(key).ok().filter(|v| !v.is_empty())
+}
+
+fn resolve_header_value(value: &str) -> String {
+    match value.strip_prefix("${").and_then(|s| s.strip_suffix('}')) {
+        Some(var) => env_var(var).unwrap_or_else(|| value.to_owned()),
+        None => value.to_owned(),
+    }
+}
+
+fn parse_protocol(value: Option<String>) -> Option<OtlpProtocol> {
+    match value?.as_str() {
+        "http/protobuf" => Some(OtlpProtocol::HttpProtobuf),
+        "http/json" => Some(OtlpProtocol::HttpJson),
+        // `grpc` is selected by the endpoint's url scheme rather than this
+        // field, so there's nothing to resolve for it here.
+        _ => None,
+    }
+}
+
+fn sdk_disabled() -> bool {
+    env_var("OTEL_SDK_DISABLED").is_some_and(|v| v.eq_ignore_ascii_case("true"))
+}
+
+// Drops any entry that isn't valid ASCII metadata rather than failing
+// provider construction over a malformed header.
+fn metadata_map(headers: &HashMap<String, String>) -> MetadataMap {
+    let mut metadata = MetadataMap::new();
+    for (key, value) in headers {
+        if let (Ok(key), Ok(value)) = (key.parse(), MetadataValue::try_from(value)) {
+            metadata.insert(key, value);
+        }
+    }
+    metadata
+}
+
 impl Default for MetricsConfig {
     fn default() -> Self {
         Self {
             export_interval: Self::default_export_interval(),
+
+            #[cfg(feature = "telemetry-server")]
+            prometheus: false,
+
+            protocol: OtlpProtocol::default(),
+            temporality: Temporality::default(),
+            views: Vec::new(),
+            headers: HashMap::new(),
         }
     }
 }
@@ -37,31 +231,93 @@ impl Default for MetricsConfig {
 #[derive(Clone)]
 pub(crate) struct MetricsProvider {
     metrics_provider: Option<OtelMeterProvider>,
+
+    #[cfg(feature = "telemetry-server")]
+    prometheus_registry: Option<prometheus::Registry>,
 }
 
 impl MetricsProvider {
     pub(crate) fn new(otel_resource: Resource, config: &Config) -> Result<Self> {
-        let metrics_provider = if let Some(uri) = config.uri.as_ref() {
-            let uri = Url::parse(uri)?;
+        if sdk_disabled() {
+            return Ok(Self {
+                metrics_provider: None,
+
+                #[cfg(feature = "telemetry-server")]
+                prometheus_registry: None,
+            });
+        }
+
+        let resolved = ResolvedMetricsConfig::resolve(config);
+
+        #[cfg(feature = "telemetry-server")]
+        let prometheus_registry = if config.metrics.prometheus {
+            Some(prometheus::Registry::new())
+        } else {
+            None
+        };
+
+        let mut builder = OtelMeterProvider::builder().with_resource(otel_resource);
+        let mut has_reader = false;
+
+        for view in &config.metrics.views {
+            let mut instrument = Instrument::new().name(view.instrument_name.clone());
+            if let Some(unit) = view.unit.as_ref() {
+                instrument = instrument.unit(unit.clone());
+            }
+            let stream = Stream::new().aggregation(Aggregation::from(view.aggregation.clone()));
+            builder = builder.with_view(new_view(instrument, stream)?);
+        }
+
+        if let Some(endpoint) = resolved.endpoint.as_ref() {
+            let uri = Url::parse(endpoint)?;
             let exporter = match uri.supported_scheme()? {
-                UriScheme::Https | UriScheme::Http => MetricExporter::builder()
-                    .with_http()
-                    .with_endpoint(uri.to_string())
-                    .build()?,
-                UriScheme::Grpc => MetricExporter::builder()
-                    .with_tonic()
-                    .with_endpoint(uri.to_string())
-                    .build()?,
+                UriScheme::Https | UriScheme::Http => {
+                    let mut builder = MetricExporter::builder()
+                        .with_http()
+                        .with_endpoint(uri.to_string())
+                        .with_protocol(resolved.protocol.into())
+                        .with_temporality(resolved.temporality.into());
+
+                    if !resolved.headers.is_empty() {
+                        builder = builder.with_headers(resolved.headers.clone());
+                    }
+
+                    builder.build()?
+                }
+                UriScheme::Grpc => {
+                    let mut builder = MetricExporter::builder()
+                        .with_tonic()
+                        .with_endpoint(uri.to_string())
+                        .with_temporality(resolved.temporality.into());
+
+                    if !resolved.headers.is_empty() {
+                        builder = builder.with_metadata(metadata_map(&resolved.headers));
+                    }
+
+                    builder.build()?
+                }
             };
 
             let reader = PeriodicReader::builder(exporter, runtime::Tokio)
-                .with_interval(config.metrics.export_interval)
+                .with_interval(resolved.export_interval)
                 .build();
 
-            let provider = OtelMeterProvider::builder()
-                .with_resource(otel_resource)
-                .with_reader(reader)
-                .build();
+            builder = builder.with_reader(reader);
+            has_reader = true;
+        }
+
+        #[cfg(feature = "telemetry-server")]
+        if let Some(registry) = prometheus_registry.as_ref() {
+            let prometheus_reader = opentelemetry_prometheus::exporter()
+                .with_registry(registry.clone())
+                .build()?;
+
+            builder = builder.with_reader(prometheus_reader);
+            has_reader = true;
+        }
+
+        let metrics_provider = if has_reader {
+            let provider = builder.build();
 
             #[cfg(feature = "otel-api")]
             opentelemetry::global::set_meter_provider(provider.clone());
@@ -71,7 +327,17 @@ impl MetricsProvider {
             None
         };
 
-        Ok(Self { metrics_provider })
+        Ok(Self {
+            metrics_provider,
+
+            #[cfg(feature = "telemetry-server")]
+            prometheus_registry,
+        })
+    }
+
+    #[cfg(feature = "telemetry-server")]
+    pub(crate) fn prometheus_registry(&self) -> Option<prometheus::Registry> {
+        self.prometheus_registry.clone()
     }
 
     pub(crate) fn layer<S>(&self) -> Option<impl Layer<S>>
@@ -105,3 +371,112 @@ macro_rules! record {
 }
 
 pub use record;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    fn clear_env() {
+        for key in [
+            "OTEL_EXPORTER_OTLP_METRICS_ENDPOINT",
+            "OTEL_EXPORTER_OTLP_ENDPOINT",
+            "OTEL_EXPORTER_OTLP_METRICS_PROTOCOL",
+            "OTEL_EXPORTER_OTLP_PROTOCOL",
+            "OTEL_EXPORTER_OTLP_METRICS_EXPORT_INTERVAL",
+            "OTEL_SDK_DISABLED",
+        ] {
+            std::env::remove_var(key);
+        }
+    }
+
+    #[test]
+    fn endpoint_prefers_metrics_specific_env_var_over_generic() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        std::env::set_var("OTEL_EXPORTER_OTLP_METRICS_ENDPOINT", "http://metrics:4318");
+        std::env::set_var("OTEL_EXPORTER_OTLP_ENDPOINT", "http://generic:4318");
+
+        let resolved = ResolvedMetricsConfig::resolve(&Config::default());
+
+        assert_eq!(resolved.endpoint.as_deref(), Some("http://metrics:4318"));
+        clear_env();
+    }
+
+    #[test]
+    fn endpoint_falls_back_to_generic_env_var() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        std::env::set_var("OTEL_EXPORTER_OTLP_ENDPOINT", "http://generic:4318");
+
+        let resolved = ResolvedMetricsConfig::resolve(&Config::default());
+
+        assert_eq!(resolved.endpoint.as_deref(), Some("http://generic:4318"));
+        clear_env();
+    }
+
+    #[test]
+    fn endpoint_prefers_env_over_configured_uri() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        std::env::set_var("OTEL_EXPORTER_OTLP_METRICS_ENDPOINT", "http://metrics:4318");
+
+        let mut config = Config::default();
+        config.uri = Some("http://configured:4318".to_owned());
+
+        let resolved = ResolvedMetricsConfig::resolve(&config);
+
+        assert_eq!(resolved.endpoint.as_deref(), Some("http://metrics:4318"));
+        clear_env();
+    }
+
+    #[test]
+    fn endpoint_falls_back_to_configured_uri_when_no_env_set() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+
+        let mut config = Config::default();
+        config.uri = Some("http://configured:4318".to_owned());
+
+        let resolved = ResolvedMetricsConfig::resolve(&config);
+
+        assert_eq!(resolved.endpoint.as_deref(), Some("http://configured:4318"));
+        clear_env();
+    }
+
+    #[test]
+    fn protocol_prefers_metrics_specific_env_var_over_generic() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        std::env::set_var("OTEL_EXPORTER_OTLP_METRICS_PROTOCOL", "http/json");
+        std::env::set_var("OTEL_EXPORTER_OTLP_PROTOCOL", "http/protobuf");
+
+        let resolved = ResolvedMetricsConfig::resolve(&Config::default());
+
+        assert_eq!(resolved.protocol, OtlpProtocol::HttpJson);
+        clear_env();
+    }
+
+    #[test]
+    fn parse_protocol_rejects_unrecognized_values() {
+        assert_eq!(parse_protocol(None), None);
+        assert_eq!(parse_protocol(Some("grpc".to_owned())), None);
+        assert_eq!(
+            parse_protocol(Some("http/json".to_owned())),
+            Some(OtlpProtocol::HttpJson)
+        );
+    }
+
+    #[test]
+    fn sdk_disabled_is_case_insensitive_and_defaults_off() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        clear_env();
+        assert!(!sdk_disabled());
+
+        std::env::set_var("OTEL_SDK_DISABLED", "True");
+        assert!(sdk_disabled());
+        clear_env();
+    }
+}
@@ -0,0 +1,27 @@
+use http::HeaderMap;
+use opentelemetry::{global, Context};
+use opentelemetry_http::{HeaderExtractor, HeaderInjector};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use tracing_opentelemetry::OpenTelemetrySpanExt as _;
+
+#[cfg(feature = "reqwest")]
+pub mod reqwest;
+
+pub(crate) fn install() {
+    global::set_text_map_propagator(TraceContextPropagator::new());
+}
+
+/// Injects the current span's `traceparent`/`tracestate` headers so a downstream
+/// service can continue the same distributed trace.
+pub fn inject_context(headers: &mut HeaderMap) {
+    let cx = tracing::Span::current().context();
+    global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&cx, &mut HeaderInjector(headers));
+    });
+}
+
+/// Extracts a parent [`Context`] from inbound `traceparent`/`tracestate` headers,
+/// falling back to the current context when none are present.
+pub fn extract_context(headers: &HeaderMap) -> Context {
+    global::get_text_map_propagator(|propagator| propagator.extract(&HeaderExtractor(headers)))
+}
@@ -0,0 +1,51 @@
+use super::inject_context;
+use async_trait::async_trait;
+use opentelemetry::trace::Status;
+use reqwest::{Request, Response};
+use reqwest_middleware::{Middleware, Next, Result};
+use task_local_extensions::Extensions;
+use tracing::Instrument;
+use tracing_opentelemetry::OpenTelemetrySpanExt as _;
+
+/// A [`reqwest_middleware`] [`Middleware`] that opens a client span around every
+/// request, propagates the active trace context via `traceparent`/`tracestate`
+/// headers, and records the outcome on the span.
+pub struct TraceContextMiddleware;
+
+#[async_trait]
+impl Middleware for TraceContextMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        let span = tracing::info_span!(
+            "http.client.request",
+            otel.kind = "client",
+            http.method = %req.method(),
+            http.url = %req.url(),
+            http.status_code = tracing::field::Empty,
+        );
+
+        {
+            let _entered = span.enter();
+            inject_context(req.headers_mut());
+        }
+
+        let res = next.run(req, extensions).instrument(span.clone()).await;
+        let _entered = span.enter();
+
+        match &res {
+            Ok(response) => {
+                span.record("http.status_code", response.status().as_u16());
+            }
+            Err(e) => {
+                span.set_status(Status::error(e.to_string()));
+                tracing::error!(error = %e, "outbound http request failed");
+            }
+        }
+
+        res
+    }
+}
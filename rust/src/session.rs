@@ -2,6 +2,7 @@ use crate::{error::Result, Config};
 use once_cell::sync::OnceCell;
 use opentelemetry::KeyValue;
 use opentelemetry_sdk::Resource;
+use std::sync::Once;
 
 static _SESSION: OnceCell<Session> = OnceCell::new();
 
@@ -9,6 +10,62 @@ pub fn init(config: &Config) -> Result<Session> {
     _SESSION.get_or_try_init(|| Session::new(config)).cloned()
 }
 
+static ERROR_HANDLER_INSTALLED: Once = Once::new();
+
+// Overrides the default handler `Session::new` installs; call before `init`.
+pub fn set_error_handler(handler: impl Fn(opentelemetry::global::Error) + Send + Sync + 'static) {
+    ERROR_HANDLER_INSTALLED.call_once(|| {});
+    let _ = opentelemetry::global::set_error_handler(handler);
+}
+
+#[cfg(any(feature = "trace", feature = "logs", feature = "metrics"))]
+fn install_default_error_handler(
+    level: crate::config::ErrorLevel,
+    logs_health: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    trace_health: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    metrics_health: std::sync::Arc<std::sync::atomic::AtomicBool>,
+) {
+    use std::sync::atomic::Ordering;
+
+    thread_local! {
+        static HANDLING: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    }
+
+    ERROR_HANDLER_INSTALLED.call_once(|| {
+        let _ = opentelemetry::global::set_error_handler(move |error| {
+            HANDLING.with(|handling| {
+                if handling.replace(true) {
+                    return;
+                }
+
+                match &error {
+                    opentelemetry::global::Error::Trace(_) => {
+                        trace_health.store(false, Ordering::Relaxed)
+                    }
+                    opentelemetry::global::Error::Log(_) => {
+                        logs_health.store(false, Ordering::Relaxed)
+                    }
+                    opentelemetry::global::Error::Metric(_) => {
+                        metrics_health.store(false, Ordering::Relaxed)
+                    }
+                    _ => {}
+                }
+
+                match level {
+                    crate::config::ErrorLevel::Error => {
+                        tracing::error!(%error, "opentelemetry internal error")
+                    }
+                    crate::config::ErrorLevel::Warn => {
+                        tracing::warn!(%error, "opentelemetry internal error")
+                    }
+                }
+
+                handling.set(false);
+            });
+        });
+    });
+}
+
 #[derive(Clone)]
 pub struct Session {
     pub otel_resource: Resource,
@@ -21,6 +78,9 @@ pub struct Session {
 
     #[cfg(feature = "metrics")]
     _metrics_provider: crate::metrics::MetricsProvider,
+
+    #[cfg(feature = "telemetry-server")]
+    _telemetry_server: Option<std::sync::Arc<crate::telemetry_server::TelemetryServer>>,
 }
 
 impl Session {
@@ -37,6 +97,18 @@ impl Session {
             ),
         ]);
 
+        let logs_health = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let trace_health = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let metrics_health = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(true));
+
+        #[cfg(any(feature = "trace", feature = "logs", feature = "metrics"))]
+        install_default_error_handler(
+            config.error_level,
+            logs_health.clone(),
+            trace_health.clone(),
+            metrics_health.clone(),
+        );
+
         #[cfg(feature = "logs")]
         let logger_provider = {
             let provider = crate::log::LoggerProvider::new(otel_resource.clone(), config)?;
@@ -44,27 +116,58 @@ impl Session {
             provider
         };
 
+        #[cfg(feature = "trace")]
+        crate::propagation::install();
+
         #[cfg(feature = "trace")]
         let tracer_provider = crate::trace::TracerProvider::new(otel_resource.clone(), config)?;
 
         #[cfg(feature = "metrics")]
         let metrics_provider = crate::metrics::MetricsProvider::new(otel_resource.clone(), config)?;
 
-        #[cfg(any(feature = "trace", feature = "metrics"))]
+        #[cfg(any(feature = "trace", feature = "metrics", feature = "logs"))]
         {
             use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
             let registry = tracing_subscriber::registry();
 
+            #[cfg(feature = "trace")]
+            let registry = registry.with(crate::trace::RequestIdLayer);
+
             #[cfg(feature = "metrics")]
             let registry = registry.with(metrics_provider.layer());
 
             #[cfg(feature = "trace")]
             let registry = registry.with(tracer_provider.layer());
 
+            #[cfg(feature = "logs")]
+            let registry = registry.with(logger_provider.layer());
+
             registry.init()
         }
 
+        #[cfg(feature = "telemetry-server")]
+        let telemetry_server = {
+            let health = crate::telemetry_server::ProviderHealth {
+                logs: logs_health.clone(),
+                trace: trace_health.clone(),
+                metrics: metrics_health.clone(),
+            };
+
+            #[cfg(feature = "metrics")]
+            let registry = metrics_provider.prometheus_registry().unwrap_or_default();
+
+            #[cfg(not(feature = "metrics"))]
+            let registry = Default::default();
+
+            crate::telemetry_server::TelemetryServer::spawn(
+                &config.telemetry_server,
+                registry,
+                health,
+            )?
+            .map(std::sync::Arc::new)
+        };
+
         Ok(Self {
             otel_resource: otel_resource.clone(),
 
@@ -76,6 +179,9 @@ impl Session {
 
             #[cfg(feature = "metrics")]
             _metrics_provider: metrics_provider,
+
+            #[cfg(feature = "telemetry-server")]
+            _telemetry_server: telemetry_server,
         })
     }
 }
@@ -0,0 +1,151 @@
+use crate::error::Result;
+use axum::{extract::State, routing::get, Json, Router};
+use prometheus::{Encoder as _, Registry, TextEncoder};
+use serde::Serialize;
+use std::{
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+use tokio::{net::TcpListener, task::JoinHandle};
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct TelemetryServerConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default = "TelemetryServerConfig::default_host")]
+    pub host: String,
+
+    #[serde(default = "TelemetryServerConfig::default_port")]
+    pub port: u16,
+}
+
+impl TelemetryServerConfig {
+    fn default_host() -> String {
+        "0.0.0.0".to_owned()
+    }
+
+    fn default_port() -> u16 {
+        9464
+    }
+}
+
+impl Default for TelemetryServerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: Self::default_host(),
+            port: Self::default_port(),
+        }
+    }
+}
+
+/// Per-provider health flags reported by the `/healthz` and `/readyz` endpoints.
+/// Each flag starts healthy and is cleared once the default OpenTelemetry error
+/// handler reports an internal error for that signal; a handler installed with
+/// `set_error_handler` leaves the flags untouched.
+#[derive(Clone, Default)]
+pub(crate) struct ProviderHealth {
+    pub logs: Arc<AtomicBool>,
+    pub trace: Arc<AtomicBool>,
+    pub metrics: Arc<AtomicBool>,
+}
+
+impl ProviderHealth {
+    fn is_ready(&self) -> bool {
+        self.logs.load(Ordering::Relaxed)
+            && self.trace.load(Ordering::Relaxed)
+            && self.metrics.load(Ordering::Relaxed)
+    }
+}
+
+#[derive(Serialize)]
+struct HealthBody {
+    logs: bool,
+    trace: bool,
+    metrics: bool,
+}
+
+#[derive(Clone)]
+struct AppState {
+    registry: Registry,
+    health: ProviderHealth,
+}
+
+pub(crate) struct TelemetryServer {
+    handle: JoinHandle<()>,
+}
+
+impl TelemetryServer {
+    pub(crate) fn spawn(
+        config: &TelemetryServerConfig,
+        registry: Registry,
+        health: ProviderHealth,
+    ) -> Result<Option<Self>> {
+        if !config.enabled {
+            return Ok(None);
+        }
+
+        let addr: SocketAddr = format!("{}:{}", config.host, config.port).parse()?;
+        let std_listener = std::net::TcpListener::bind(addr)?;
+        std_listener.set_nonblocking(true)?;
+        let listener = TcpListener::from_std(std_listener)?;
+
+        let state = AppState { registry, health };
+        let app = Router::new()
+            .route("/metrics", get(metrics_handler))
+            .route("/healthz", get(healthz_handler))
+            .route("/readyz", get(readyz_handler))
+            .with_state(state);
+
+        let handle = tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                eprintln!("telemetry server exited: {e}");
+            }
+        });
+
+        Ok(Some(Self { handle }))
+    }
+}
+
+impl Drop for TelemetryServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+async fn metrics_handler(State(state): State<AppState>) -> String {
+    let metric_families = state.registry.gather();
+    let mut buf = Vec::new();
+    if let Err(e) = TextEncoder::new().encode(&metric_families, &mut buf) {
+        eprintln!("failed to encode prometheus metrics: {e}");
+    }
+    String::from_utf8(buf).unwrap_or_default()
+}
+
+async fn healthz_handler(State(state): State<AppState>) -> Json<HealthBody> {
+    Json(HealthBody {
+        logs: state.health.logs.load(Ordering::Relaxed),
+        trace: state.health.trace.load(Ordering::Relaxed),
+        metrics: state.health.metrics.load(Ordering::Relaxed),
+    })
+}
+
+async fn readyz_handler(State(state): State<AppState>) -> (axum::http::StatusCode, Json<HealthBody>) {
+    let body = HealthBody {
+        logs: state.health.logs.load(Ordering::Relaxed),
+        trace: state.health.trace.load(Ordering::Relaxed),
+        metrics: state.health.metrics.load(Ordering::Relaxed),
+    };
+
+    let status = if state.health.is_ready() {
+        axum::http::StatusCode::OK
+    } else {
+        axum::http::StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(body))
+}
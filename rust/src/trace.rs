@@ -1,14 +1,20 @@
 use crate::{
-    config::{Config, UriScheme, UrlExt},
+    config::{BatchExportConfig, Config, ExportMode, UriScheme, UrlExt},
     error::Result,
 };
 use opentelemetry::trace::TracerProvider as _;
 use opentelemetry_otlp::{SpanExporter, WithExportConfig};
-use opentelemetry_sdk::{runtime, trace::TracerProvider as OtelTracerProvider, Resource};
+use opentelemetry_sdk::{
+    runtime,
+    trace::{BatchSpanProcessor, Sampler, SimpleSpanProcessor, TracerProvider as OtelTracerProvider},
+    Resource,
+};
+use std::{cell::RefCell, sync::Arc};
 use tracing::{level_filters::LevelFilter, Subscriber};
 use tracing_opentelemetry::OpenTelemetryLayer;
-use tracing_subscriber::{registry::LookupSpan, Layer};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
 use url::Url;
+use uuid::Uuid;
 
 // rexporting trace macros
 pub use {
@@ -22,6 +28,52 @@ pub use {
 pub struct TraceConfig {
     #[serde(default)]
     pub level: TraceLevel,
+
+    #[serde(default)]
+    pub sampler: SamplerConfig,
+
+    #[serde(default)]
+    pub export_mode: ExportMode,
+
+    #[serde(default)]
+    pub batch: BatchExportConfig,
+}
+
+#[derive(serde::Deserialize, Debug, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum SamplerConfig {
+    AlwaysOn,
+    AlwaysOff,
+    TraceIdRatio(f64),
+    ParentBased(Box<SamplerConfig>),
+}
+
+impl Default for SamplerConfig {
+    fn default() -> Self {
+        Self::ParentBased(Box::new(Self::AlwaysOn))
+    }
+}
+
+impl From<SamplerConfig> for Sampler {
+    fn from(value: SamplerConfig) -> Self {
+        match value {
+            SamplerConfig::AlwaysOn => Sampler::AlwaysOn,
+            SamplerConfig::AlwaysOff => Sampler::AlwaysOff,
+            SamplerConfig::TraceIdRatio(ratio) => {
+                let ratio = ratio.clamp(0.0, 1.0);
+                if ratio >= 1.0 {
+                    Sampler::AlwaysOn
+                } else if ratio <= 0.0 {
+                    Sampler::AlwaysOff
+                } else {
+                    Sampler::TraceIdRatioBased(ratio)
+                }
+            }
+            SamplerConfig::ParentBased(inner) => {
+                Sampler::ParentBased(Box::new(Sampler::from(*inner)))
+            }
+        }
+    }
 }
 
 #[derive(serde::Deserialize, Debug, Clone, Copy)]
@@ -74,10 +126,23 @@ impl TracerProvider {
                     .build()?,
             };
 
-            let provider = OtelTracerProvider::builder()
+            let builder = OtelTracerProvider::builder()
                 .with_resource(otel_resource)
-                .with_batch_exporter(exporter, runtime::Tokio)
-                .build();
+                .with_sampler(Sampler::from(config.trace.sampler.clone()));
+
+            let provider = match config.trace.export_mode {
+                ExportMode::Simple => {
+                    builder.with_span_processor(SimpleSpanProcessor::new(Box::new(exporter)))
+                }
+                ExportMode::Batch => {
+                    let processor = BatchSpanProcessor::builder(exporter, runtime::Tokio)
+                        .with_batch_config(crate::config::build_batch_config(&config.trace.batch))
+                        .build();
+
+                    builder.with_span_processor(processor)
+                }
+            }
+            .build();
 
             #[cfg(feature = "otel-api")]
             opentelemetry::global::set_tracer_provider(provider.clone());
@@ -115,3 +180,106 @@ impl Drop for TracerProvider {
         }
     }
 }
+
+thread_local! {
+    static SEEDED_REQUEST_ID: RefCell<Option<Arc<str>>> = const { RefCell::new(None) };
+}
+
+#[derive(Clone)]
+pub(crate) struct RequestId(pub Arc<str>);
+
+pub fn new_request_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+// Seeds the id that root spans created on this thread while `f` runs pick up,
+// so an inbound handler can continue an id from an upstream header instead of
+// minting one. Spans with a parent keep inheriting the parent's id.
+pub fn with_request_id<R>(id: impl Into<String>, f: impl FnOnce() -> R) -> R {
+    let previous = SEEDED_REQUEST_ID.with(|cell| cell.replace(Some(Arc::from(id.into()))));
+    let result = f();
+    SEEDED_REQUEST_ID.with(|cell| *cell.borrow_mut() = previous);
+    result
+}
+
+pub(crate) fn current_request_id() -> Option<Arc<str>> {
+    tracing::dispatcher::get_default(|dispatch| {
+        let registry = dispatch.downcast_ref::<tracing_subscriber::Registry>()?;
+        let id = tracing::Span::current().id()?;
+        let span = registry.span(&id)?;
+        span.extensions().get::<RequestId>().map(|r| r.0.clone())
+    })
+}
+
+// Propagates request.id from a parent span to its children, seeding root
+// spans from `with_request_id` or, failing that, a freshly generated id.
+pub(crate) struct RequestIdLayer;
+
+impl<S> Layer<S> for RequestIdLayer
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(&self, _attrs: &tracing::span::Attributes<'_>, id: &tracing::span::Id, ctx: Context<'_, S>) {
+        let span = ctx.span(id).expect("span must exist in the registry it was just created in");
+
+        if span.extensions().get::<RequestId>().is_some() {
+            return;
+        }
+
+        let inherited = span
+            .parent()
+            .and_then(|parent| parent.extensions().get::<RequestId>().cloned());
+
+        let request_id = inherited.unwrap_or_else(|| {
+            let seeded = SEEDED_REQUEST_ID.with(|cell| cell.borrow().clone());
+            RequestId(seeded.unwrap_or_else(|| Arc::from(new_request_id())))
+        });
+
+        span.extensions_mut().insert(request_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ratio_zero_becomes_always_off() {
+        assert!(matches!(
+            Sampler::from(SamplerConfig::TraceIdRatio(0.0)),
+            Sampler::AlwaysOff
+        ));
+    }
+
+    #[test]
+    fn ratio_one_becomes_always_on() {
+        assert!(matches!(
+            Sampler::from(SamplerConfig::TraceIdRatio(1.0)),
+            Sampler::AlwaysOn
+        ));
+    }
+
+    #[test]
+    fn ratio_above_one_is_clamped_to_always_on() {
+        assert!(matches!(
+            Sampler::from(SamplerConfig::TraceIdRatio(1.5)),
+            Sampler::AlwaysOn
+        ));
+    }
+
+    #[test]
+    fn ratio_below_zero_is_clamped_to_always_off() {
+        assert!(matches!(
+            Sampler::from(SamplerConfig::TraceIdRatio(-0.5)),
+            Sampler::AlwaysOff
+        ));
+    }
+
+    #[test]
+    fn ratio_in_between_stays_trace_id_ratio_based() {
+        match Sampler::from(SamplerConfig::TraceIdRatio(0.5)) {
+            Sampler::TraceIdRatioBased(ratio) => assert_eq!(ratio, 0.5),
+            _ => panic!("expected TraceIdRatioBased"),
+        }
+    }
+}